@@ -0,0 +1,143 @@
+//! Git blame attribution for scanned entries.
+//!
+//! Blame is resolved with `git2` (libgit2) rather than shelling out to `git blame`
+//! once per line: `Repository::blame_file` walks history once per *file* and hands
+//! back hunks covering every line, so a file with a dozen TODOs costs one blame
+//! pass instead of a dozen subprocesses.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Attribution for a single blamed line.
+#[derive(Debug, Clone)]
+pub struct BlameInfo {
+    pub author: String,
+    pub commit: String,
+    pub committed_at: String,
+}
+
+/// Blame every line in `target_lines` (1-based) of `file`, relative to the git
+/// repository `file` lives in. Returns attribution only for lines git could
+/// resolve; lines outside the blamed range are omitted. A file that is not in a
+/// repository, or that git cannot blame, is an error.
+pub fn blame_lines(file: &Path, target_lines: &[usize]) -> Result<HashMap<usize, BlameInfo>> {
+    let repo = git2::Repository::discover(file)
+        .with_context(|| format!("{} is not inside a git repository", file.display()))?;
+
+    let repo_root = repo
+        .workdir()
+        .context("Repository has no working directory")?;
+
+    let rel_path = file.strip_prefix(repo_root).unwrap_or(file);
+
+    let blame = repo
+        .blame_file(rel_path, None)
+        .with_context(|| format!("Failed to blame {}", file.display()))?;
+
+    let mut result = HashMap::new();
+
+    for &line in target_lines {
+        let Some(hunk) = blame.get_line(line) else {
+            continue;
+        };
+
+        let commit_id = hunk.final_commit_id();
+        let commit = repo
+            .find_commit(commit_id)
+            .with_context(|| format!("Failed to look up commit {}", commit_id))?;
+
+        let author = commit.author();
+        let name = author.name().unwrap_or("unknown").to_string();
+
+        result.insert(
+            line,
+            BlameInfo {
+                author: name,
+                commit: short_hash(&commit_id),
+                committed_at: format_git_time(commit.time()),
+            },
+        );
+    }
+
+    Ok(result)
+}
+
+fn short_hash(oid: &git2::Oid) -> String {
+    let full = oid.to_string();
+    full[..full.len().min(7)].to_string()
+}
+
+fn format_git_time(time: git2::Time) -> String {
+    let dt = chrono::DateTime::<chrono::Utc>::from_timestamp(time.seconds(), 0)
+        .unwrap_or_default()
+        .with_timezone(&chrono::FixedOffset::east_opt(time.offset_minutes() * 60).unwrap_or(chrono::FixedOffset::east_opt(0).unwrap()));
+
+    dt.to_rfc3339()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    /// Initialize a fresh git repo in a temp dir, write `content` to `test.rs`, and
+    /// commit it, so `blame_lines` has real history to resolve against.
+    fn init_repo_with_commit(content: &str) -> (TempDir, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test Author").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+
+        let file_path = dir.path().join("test.rs");
+        fs::write(&file_path, content).unwrap();
+
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("test.rs")).unwrap();
+        index.write().unwrap();
+        let tree_id = index.write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let sig = repo.signature().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "initial commit", &tree, &[])
+            .unwrap();
+
+        (dir, file_path)
+    }
+
+    #[test]
+    fn test_short_hash_truncates_to_seven_chars() {
+        let oid = git2::Oid::from_str("0123456789abcdef0123456789abcdef01234567").unwrap();
+        assert_eq!(short_hash(&oid), "0123456");
+    }
+
+    #[test]
+    fn test_format_git_time_renders_rfc3339() {
+        let time = git2::Time::new(0, 0);
+        assert_eq!(format_git_time(time), "1970-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_blame_lines_resolves_author_and_commit() {
+        let (_dir, file_path) = init_repo_with_commit("// TODO: fix this\nlet x = 1;\n");
+
+        let result = blame_lines(&file_path, &[1, 2]).unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[&1].author, "Test Author");
+        assert_eq!(result[&1].commit.len(), 7);
+    }
+
+    #[test]
+    fn test_blame_lines_omits_out_of_range_lines() {
+        let (_dir, file_path) = init_repo_with_commit("// TODO: fix this\n");
+
+        let result = blame_lines(&file_path, &[1, 99]).unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert!(result.contains_key(&1));
+    }
+}
@@ -0,0 +1,347 @@
+//! Mutating "fix" operations that rewrite matched TODO/FIXME lines in place.
+//!
+//! Unlike the rest of the scanner, these operate on the *original* file contents
+//! rather than the trimmed `TodoEntry::text`, since they need to preserve
+//! indentation and line endings when rewriting a line.
+
+use crate::TodoEntry;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// What a `--fix`-style invocation should do to matched lines.
+#[derive(Debug, Clone)]
+pub enum FixKind {
+    /// Turn a bare `KEYWORD:` into `KEYWORD(name):`.
+    Assign(String),
+    /// Delete the whole comment line for a given keyword.
+    Strip(String),
+}
+
+/// A single line-level change, planned against the original file content.
+/// `after: None` means the line should be deleted.
+#[derive(Debug, Clone)]
+pub struct PlannedEdit {
+    pub file_path: PathBuf,
+    pub line_number: usize,
+    pub before: String,
+    pub after: Option<String>,
+}
+
+/// Plan the edits implied by `kind` for every entry whose original file content is
+/// present in `contents`. Entries without an edit to make (e.g. already-assigned
+/// TODOs, or a strip for a different keyword) are skipped.
+pub fn plan_edits(
+    entries: &[TodoEntry],
+    contents: &HashMap<PathBuf, String>,
+    kind: &FixKind,
+) -> Vec<PlannedEdit> {
+    let mut edits = Vec::new();
+
+    for entry in entries {
+        let Some(content) = contents.get(&entry.file_path) else {
+            continue;
+        };
+        let Some(line) = content.lines().nth(entry.line_number - 1) else {
+            continue;
+        };
+
+        let after = match kind {
+            FixKind::Assign(name) => assign_line(line, &entry.keyword, name),
+            FixKind::Strip(keyword) => {
+                if entry.keyword.eq_ignore_ascii_case(keyword) && is_whole_line_comment(line, entry) {
+                    Some(None)
+                } else {
+                    None
+                }
+            }
+        };
+
+        if let Some(after) = after {
+            edits.push(PlannedEdit {
+                file_path: entry.file_path.clone(),
+                line_number: entry.line_number,
+                before: line.to_string(),
+                after,
+            });
+        }
+    }
+
+    edits
+}
+
+/// Whether `line` is *entirely* a comment for `entry`, so deleting it can't also
+/// delete code, e.g. `let x = 5; // TODO drop` is not a whole-line comment even
+/// though its `TodoEntry` was matched through comment-aware detection - only
+/// `// TODO drop` on its own line is. Entries with no detected `comment_style`
+/// (no known comment syntax for the file, or `--all-lines` matched bare text)
+/// are never considered whole-line comments, since we can't tell where code ends.
+fn is_whole_line_comment(line: &str, entry: &TodoEntry) -> bool {
+    let Some(style) = entry.comment_style.as_deref() else {
+        return false;
+    };
+    let trimmed = line.trim();
+    if !trimmed.starts_with(style) {
+        return false;
+    }
+    if entry.comment_kind != Some(crate::CommentKind::Block) {
+        return true;
+    }
+    // A block comment covers the whole line only if it also closes at its end.
+    let ext = entry.file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let close = crate::language_syntax(ext)
+        .and_then(|syntax| syntax.block_comments.iter().find(|(open, _)| *open == style))
+        .map(|(_, close)| *close);
+    match close {
+        Some(close) => trimmed[style.len()..]
+            .find(close)
+            .is_some_and(|i| style.len() + i + close.len() == trimmed.len()),
+        None => false,
+    }
+}
+
+/// Insert `(name)` before the colon of a bare `keyword:` comment, returning the
+/// rewritten line. Returns `None` if `keyword` isn't followed by a bare colon
+/// (e.g. it's already assigned, as in `TODO(bob):`).
+fn assign_line(line: &str, keyword: &str, name: &str) -> Option<Option<String>> {
+    let idx = line.find(keyword)?;
+    let after_keyword = &line[idx + keyword.len()..];
+    let trimmed = after_keyword.trim_start();
+
+    if !trimmed.starts_with(':') {
+        return None;
+    }
+
+    let colon_at = idx + keyword.len() + (after_keyword.len() - trimmed.len());
+    let rewritten = format!("{}({}){}", &line[..colon_at], name, &line[colon_at..]);
+    Some(Some(rewritten))
+}
+
+/// Render `edits` as a unified-diff-style preview, grouped by file, for `--dry-run`.
+pub fn render_diff(edits: &[PlannedEdit]) -> String {
+    let mut by_file: BTreeMap<&Path, Vec<&PlannedEdit>> = BTreeMap::new();
+    for edit in edits {
+        by_file.entry(edit.file_path.as_path()).or_default().push(edit);
+    }
+
+    let mut output = String::new();
+    for (file, file_edits) in by_file {
+        output.push_str(&format!("--- {}\n+++ {}\n", file.display(), file.display()));
+        for edit in file_edits {
+            output.push_str(&format!("@@ line {} @@\n", edit.line_number));
+            output.push_str(&format!("-{}\n", edit.before));
+            if let Some(after) = &edit.after {
+                output.push_str(&format!("+{}\n", after));
+            }
+        }
+    }
+    output
+}
+
+/// Apply `edits` to disk, one file at a time. Each file is rewritten atomically:
+/// the new content is written to a sibling temp file, then renamed over the
+/// original, so a crash mid-write never leaves a half-edited file behind.
+pub fn apply_edits(contents: &HashMap<PathBuf, String>, edits: &[PlannedEdit]) -> Result<()> {
+    let mut by_file: HashMap<&Path, Vec<&PlannedEdit>> = HashMap::new();
+    for edit in edits {
+        by_file.entry(edit.file_path.as_path()).or_default().push(edit);
+    }
+
+    for (file, file_edits) in by_file {
+        let content = contents
+            .get(file)
+            .with_context(|| format!("Missing original content for {}", file.display()))?;
+
+        let line_ending = if content.contains("\r\n") { "\r\n" } else { "\n" };
+        let mut lines: Vec<Option<&str>> = content.lines().map(Some).collect();
+
+        for edit in file_edits {
+            if let Some(slot) = lines.get_mut(edit.line_number - 1) {
+                *slot = edit.after.as_deref();
+            }
+        }
+
+        let mut new_content = lines
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(line_ending);
+        if content.ends_with('\n') {
+            new_content.push_str(line_ending);
+        }
+
+        let tmp_path = file.with_file_name(format!(
+            ".{}.tmp",
+            file.file_name().and_then(|n| n.to_str()).unwrap_or("todo-scan")
+        ));
+
+        std::fs::write(&tmp_path, new_content)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        std::fs::rename(&tmp_path, file)
+            .with_context(|| format!("Failed to replace {}", file.display()))?;
+    }
+
+    Ok(())
+}
+
+/// A minimal tracked-issue draft, one per entry, for turning TODOs into real
+/// tickets (`--promote`).
+#[derive(Debug, Serialize)]
+struct IssueDraft {
+    title: String,
+    file: String,
+    line: usize,
+    keyword: String,
+}
+
+/// Render `entries` as a JSON report of issue drafts suitable for feeding into an
+/// issue tracker's bulk-create API.
+pub fn promote_report(entries: &[TodoEntry]) -> Result<String> {
+    let drafts: Vec<IssueDraft> = entries
+        .iter()
+        .map(|entry| IssueDraft {
+            title: entry.text.clone(),
+            file: entry.file_path.display().to_string(),
+            line: entry.line_number,
+            keyword: entry.keyword.clone(),
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&drafts).context("Failed to serialize promote report")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TodoEntry;
+
+    fn entry_at(path: &str, line_number: usize, keyword: &str, text: &str) -> TodoEntry {
+        TodoEntry::new(path, line_number, keyword, text)
+    }
+
+    #[test]
+    fn test_assign_line_inserts_name_before_colon() {
+        let rewritten = assign_line("    // TODO: fix this", "TODO", "alice");
+        assert_eq!(rewritten, Some(Some("    // TODO(alice): fix this".to_string())));
+    }
+
+    #[test]
+    fn test_assign_line_skips_already_assigned() {
+        let rewritten = assign_line("    // TODO(bob): fix this", "TODO", "alice");
+        assert_eq!(rewritten, None);
+    }
+
+    #[test]
+    fn test_plan_edits_assign_rewrites_bare_todo() {
+        let mut contents = HashMap::new();
+        contents.insert(
+            PathBuf::from("test.rs"),
+            "    // TODO: fix this\n".to_string(),
+        );
+        let entry = entry_at("test.rs", 1, "TODO", "TODO: fix this");
+
+        let edits = plan_edits(&[entry], &contents, &FixKind::Assign("alice".to_string()));
+
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].after.as_deref(), Some("    // TODO(alice): fix this"));
+    }
+
+    #[test]
+    fn test_plan_edits_assign_skips_already_assigned_todo() {
+        let mut contents = HashMap::new();
+        contents.insert(
+            PathBuf::from("test.rs"),
+            "    // TODO(bob): fix this\n".to_string(),
+        );
+        let entry = entry_at("test.rs", 1, "TODO", "TODO(bob): fix this");
+
+        let edits = plan_edits(&[entry], &contents, &FixKind::Assign("alice".to_string()));
+
+        assert!(edits.is_empty());
+    }
+
+    #[test]
+    fn test_plan_edits_strip_deletes_whole_line_comment() {
+        let mut contents = HashMap::new();
+        contents.insert(
+            PathBuf::from("test.rs"),
+            "    // TODO: drop this\nlet x = 5;\n".to_string(),
+        );
+        let mut entry = entry_at("test.rs", 1, "TODO", "TODO: drop this");
+        entry = entry.with_comment_style("//");
+
+        let edits = plan_edits(&[entry], &contents, &FixKind::Strip("TODO".to_string()));
+
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].after, None);
+    }
+
+    #[test]
+    fn test_plan_edits_strip_never_deletes_trailing_code_comment() {
+        let mut contents = HashMap::new();
+        contents.insert(
+            PathBuf::from("test.rs"),
+            "let x = 5; // TODO drop\n".to_string(),
+        );
+        let mut entry = entry_at("test.rs", 1, "TODO", "let x = 5; // TODO drop");
+        entry = entry.with_comment_style("//");
+
+        let edits = plan_edits(&[entry], &contents, &FixKind::Strip("TODO".to_string()));
+
+        assert!(edits.is_empty());
+    }
+
+    #[test]
+    fn test_apply_edits_preserves_indentation_and_line_endings() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("test.rs");
+        std::fs::write(&file_path, "fn main() {\r\n    // TODO: fix this\r\n}\r\n").unwrap();
+
+        let mut contents = HashMap::new();
+        contents.insert(
+            file_path.clone(),
+            std::fs::read_to_string(&file_path).unwrap(),
+        );
+
+        let edits = vec![PlannedEdit {
+            file_path: file_path.clone(),
+            line_number: 2,
+            before: "    // TODO: fix this".to_string(),
+            after: Some("    // TODO(alice): fix this".to_string()),
+        }];
+
+        apply_edits(&contents, &edits).unwrap();
+
+        let new_content = std::fs::read_to_string(&file_path).unwrap();
+        assert_eq!(
+            new_content,
+            "fn main() {\r\n    // TODO(alice): fix this\r\n}\r\n"
+        );
+    }
+
+    #[test]
+    fn test_apply_edits_strip_removes_line_entirely() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("test.rs");
+        std::fs::write(&file_path, "fn main() {\n    // TODO: drop\n}\n").unwrap();
+
+        let mut contents = HashMap::new();
+        contents.insert(
+            file_path.clone(),
+            std::fs::read_to_string(&file_path).unwrap(),
+        );
+
+        let edits = vec![PlannedEdit {
+            file_path: file_path.clone(),
+            line_number: 2,
+            before: "    // TODO: drop".to_string(),
+            after: None,
+        }];
+
+        apply_edits(&contents, &edits).unwrap();
+
+        let new_content = std::fs::read_to_string(&file_path).unwrap();
+        assert_eq!(new_content, "fn main() {\n}\n");
+    }
+}
@@ -1,10 +1,14 @@
+mod blame;
+pub mod fix;
+
 use anyhow::{Context, Result};
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
+use rayon::prelude::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use tokio::fs::File;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use walkdir::WalkDir;
 
 /// A scanned TODO/FIXME entry
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -13,8 +17,42 @@ pub struct TodoEntry {
     pub line_number: usize,
     pub keyword: String,
     pub text: String,
+    /// Byte offset of `keyword` within `text`, used to point annotations at the
+    /// exact match (see [`format_results_pretty`]).
+    pub column: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub context: Option<String>,
+    /// 1-based line number of `context`'s first line, when `context` is set. Lets
+    /// formatters (e.g. [`format_results_pretty`]) render the surrounding source
+    /// with correct gutter line numbers instead of just the matched line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_start_line: Option<usize>,
+    /// The comment delimiter the keyword was found under (e.g. `"//"`, `"#"`),
+    /// when the match was resolved through language-aware comment detection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment_style: Option<String>,
+    /// Whether `comment_style` is a line or block comment delimiter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment_kind: Option<CommentKind>,
+    /// Author name from `git blame`, populated when scanning with `--blame`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Short commit hash from `git blame`, populated when scanning with `--blame`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// RFC 3339 commit timestamp from `git blame`, populated when scanning with `--blame`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub committed_at: Option<String>,
+    /// Owner parsed from a `KEYWORD(name):` annotation, e.g. `alice` in `TODO(alice):`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    /// Issue/ticket reference parsed from the comment text, e.g. `#123` in
+    /// `FIXME(#123):` or `JIRA-456` anywhere in the text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_ref: Option<String>,
+    /// Due date parsed from the first `[YYYY-MM-DD]` tag anywhere in the comment text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
 }
 
 impl TodoEntry {
@@ -29,12 +67,30 @@ impl TodoEntry {
             line_number,
             keyword: keyword.into(),
             text: text.into(),
+            column: 0,
             context: None,
+            context_start_line: None,
+            comment_style: None,
+            comment_kind: None,
+            author: None,
+            commit: None,
+            committed_at: None,
+            assignee: None,
+            issue_ref: None,
+            due_date: None,
         }
     }
 
-    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+    /// Attach surrounding source, along with the 1-based line number of its first
+    /// line, as produced by [`TodoScanner::build_context`](crate::TodoScanner).
+    pub fn with_context(mut self, context: impl Into<String>, start_line: usize) -> Self {
         self.context = Some(context.into());
+        self.context_start_line = Some(start_line);
+        self
+    }
+
+    pub fn with_comment_style(mut self, comment_style: impl Into<String>) -> Self {
+        self.comment_style = Some(comment_style.into());
         self
     }
 }
@@ -48,6 +104,30 @@ pub struct ScanConfig {
     pub context_lines: usize,
     pub respect_gitignore: bool,
     pub max_file_size: usize,
+    /// Number of worker threads to use for directory scans. `0` means use the
+    /// number of available CPUs, as reported by `std::thread::available_parallelism`.
+    pub threads: usize,
+    /// Only report keywords that fall inside a real comment (per [`language_syntax`]
+    /// for the file's extension). Files with no known comment syntax (plain text,
+    /// data formats, etc.) are always scanned in full, since "comment" is meaningless
+    /// for them. Defaults to `false` (the original permissive behavior, matching any
+    /// line) so existing library consumers aren't affected by comment-aware scanning;
+    /// the CLI opts into `true` by default and exposes `--all-lines` to opt back out.
+    pub comments_only: bool,
+    /// An additional gitignore-style file to apply on top of `.gitignore`/`.ignore`,
+    /// rooted at each scanned directory (e.g. a project-wide ignore list that isn't
+    /// itself checked into version control).
+    pub ignore_file: Option<PathBuf>,
+    /// Keep only entries assigned to this owner (case-insensitive match against
+    /// [`TodoEntry::assignee`]), e.g. to answer "which TODOs are assigned to me".
+    pub assignee_filter: Option<String>,
+    /// Keep only entries that have (when `true`) or lack (when `false`) an
+    /// [`TodoEntry::issue_ref`], e.g. to find unowned/untracked TODOs.
+    pub require_issue_ref: Option<bool>,
+    /// Maximum number of files scanned concurrently by
+    /// [`TodoScanner::scan_directory_buffered`]. `0` means use the number of
+    /// available CPUs, as reported by `std::thread::available_parallelism`.
+    pub concurrency: usize,
 }
 
 impl Default for ScanConfig {
@@ -59,14 +139,253 @@ impl Default for ScanConfig {
             context_lines: 0,
             respect_gitignore: true,
             max_file_size: 10 * 1024 * 1024, // 10MB
+            threads: 0,
+            comments_only: false,
+            ignore_file: None,
+            assignee_filter: None,
+            require_issue_ref: None,
+            concurrency: 0,
         }
     }
 }
 
+/// Number of candidate files handed to each rayon worker per batch.
+const SCAN_CHUNK_SIZE: usize = 32;
+
+/// Comment and string delimiters for a language, keyed by file extension in
+/// [`language_syntax`]. `string_delims` lets the scanner step over string/char
+/// literals so a comment token that happens to appear inside one (e.g. `"// not a
+/// comment"`) doesn't falsely open a comment.
+struct LanguageSyntax {
+    line_comments: &'static [&'static str],
+    block_comments: &'static [(&'static str, &'static str)],
+    string_delims: &'static [char],
+}
+
+/// Look up the comment syntax for a file extension. Returns `None` for extensions
+/// with no well-defined comment syntax (e.g. plain text or data formats), in which
+/// case the whole line is treated as scannable.
+fn language_syntax(extension: &str) -> Option<&'static LanguageSyntax> {
+    const C_LIKE: LanguageSyntax = LanguageSyntax {
+        line_comments: &["//"],
+        block_comments: &[("/*", "*/")],
+        string_delims: &['"', '\''],
+    };
+    const HASH: LanguageSyntax = LanguageSyntax {
+        line_comments: &["#"],
+        block_comments: &[],
+        string_delims: &['"', '\''],
+    };
+    const SQL_LIKE: LanguageSyntax = LanguageSyntax {
+        line_comments: &["--"],
+        block_comments: &[("/*", "*/")],
+        string_delims: &['\''],
+    };
+    const LUA: LanguageSyntax = LanguageSyntax {
+        line_comments: &["--"],
+        block_comments: &[("--[[", "]]")],
+        string_delims: &['"', '\''],
+    };
+    const MARKUP: LanguageSyntax = LanguageSyntax {
+        line_comments: &[],
+        block_comments: &[("<!--", "-->")],
+        string_delims: &[],
+    };
+
+    match extension {
+        "rs" | "go" | "java" | "c" | "cpp" | "h" | "hpp" | "cs" | "js" | "ts" | "swift" | "kt"
+        | "scala" | "php" | "css" | "scss" | "less" => Some(&C_LIKE),
+        "py" | "rb" | "sh" | "bash" | "zsh" | "fish" | "r" | "yaml" | "yml" | "toml" => {
+            Some(&HASH)
+        }
+        "sql" => Some(&SQL_LIKE),
+        "lua" => Some(&LUA),
+        "html" | "xml" => Some(&MARKUP),
+        _ => None,
+    }
+}
+
+/// Whether a matched keyword was found under a single-line or a block comment
+/// delimiter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentKind {
+    Line,
+    Block,
+}
+
+/// Tracks whether we're still inside an unterminated block comment from a previous
+/// line, so multi-line comments are recognized correctly across the whole file.
+#[derive(Default)]
+struct CommentState {
+    open_block: Option<&'static str>,
+}
+
+/// A single comment span (byte range) found on a line.
+type CommentSpan = (usize, usize, &'static str);
+
+/// Find the end of a quoted string literal starting at `start` (the index of the
+/// opening `quote`), honoring `\`-escaped quotes. Returns the index just past the
+/// closing quote, or the end of the line if it's unterminated.
+fn find_string_end(line: &str, start: usize, quote: char) -> usize {
+    let mut chars = line[start + quote.len_utf8()..].char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c == '\\' {
+            chars.next();
+            continue;
+        }
+        if c == quote {
+            return start + quote.len_utf8() + i + c.len_utf8();
+        }
+    }
+    line.len()
+}
+
+/// Resolve which byte ranges of `line` are inside a comment, given `syntax` and the
+/// carried-over `state` from previous lines. Handles delimiters appearing mid-line,
+/// unterminated block comments that continue onto subsequent lines, and comment
+/// tokens that appear inside string literals (which are stepped over, not treated
+/// as comments).
+fn comment_spans(line: &str, syntax: &LanguageSyntax, state: &mut CommentState) -> Vec<CommentSpan> {
+    let mut spans = Vec::new();
+    let mut pos = 0;
+
+    while pos <= line.len() {
+        if let Some(close) = state.open_block {
+            match line[pos..].find(close) {
+                Some(idx) => {
+                    let end = pos + idx + close.len();
+                    spans.push((pos, end, close));
+                    pos = end;
+                    state.open_block = None;
+                }
+                None => {
+                    spans.push((pos, line.len(), close));
+                    return spans;
+                }
+            }
+            continue;
+        }
+
+        let string_opener = syntax
+            .string_delims
+            .iter()
+            .filter_map(|q| line[pos..].find(*q).map(|i| (pos + i, *q)))
+            .min_by_key(|(i, _)| *i);
+
+        let line_opener = syntax
+            .line_comments
+            .iter()
+            .filter_map(|tok| line[pos..].find(tok).map(|i| (pos + i, *tok)))
+            .min_by_key(|(i, _)| *i);
+
+        let block_opener = syntax
+            .block_comments
+            .iter()
+            .filter_map(|(open, close)| line[pos..].find(open).map(|i| (pos + i, *open, *close)))
+            .min_by_key(|(i, _, _)| *i);
+
+        let earliest_comment = match (line_opener, block_opener) {
+            (None, None) => None,
+            (Some((li, tok)), None) => Some((li, tok, None)),
+            (None, Some((bi, open, close))) => Some((bi, open, Some(close))),
+            (Some((li, tok)), Some((bi, open, close))) => {
+                // On a tie (e.g. Lua's line comment "--" is a prefix of its block
+                // comment "--[["), prefer the block opener: it's the more specific
+                // match at that position, and line comments never carry state across
+                // lines, so picking "--" here would make a real "--[[ ... ]]" block
+                // invisible to the unterminated-block tracking below.
+                if li < bi {
+                    Some((li, tok, None))
+                } else {
+                    Some((bi, open, Some(close)))
+                }
+            }
+        };
+
+        match (string_opener, earliest_comment) {
+            (Some((si, quote)), comment) if comment.map_or(true, |(ci, _, _)| si <= ci) => {
+                pos = find_string_end(line, si, quote);
+            }
+            (_, None) => return spans,
+            (_, Some((idx, open, None))) => {
+                spans.push((idx, line.len(), open));
+                return spans;
+            }
+            (_, Some((idx, open, Some(close)))) => {
+                let body_start = idx + open.len();
+                match line[body_start..].find(close) {
+                    Some(ci) => {
+                        let end = body_start + ci + close.len();
+                        spans.push((idx, end, open));
+                        pos = end;
+                    }
+                    None => {
+                        spans.push((idx, line.len(), open));
+                        state.open_block = Some(close);
+                        return spans;
+                    }
+                }
+            }
+        }
+    }
+
+    spans
+}
+
+/// Tech abbreviations that match the `PREFIX-NUMBER` ticket shape but aren't issue
+/// trackers, so a comment mentioning them doesn't get misread as having an
+/// `issue_ref` (e.g. `// TODO: decode as UTF-8` is not ticket `UTF-8`).
+const TICKET_FALSE_POSITIVES: &[&str] = &["UTF", "SHA", "ISO", "ASCII", "HTTP", "HTTPS", "MD5"];
+
+/// Parse structured annotations out of a matched comment's trimmed `text`, e.g.
+/// `TODO(alice): fix this [2024-06-01]` or `FIXME(#123): JIRA-456 still open`.
+/// Returns `(assignee, issue_ref, due_date)`. A parenthesized tag right after
+/// `keyword` is treated as an `issue_ref` if it looks like an issue number (`#123`
+/// or starts with a digit), and as an `assignee` otherwise.
+fn parse_annotations(text: &str, keyword: &str) -> (Option<String>, Option<String>, Option<String>) {
+    let mut assignee = None;
+    let mut issue_ref = None;
+
+    if let Ok(tag_regex) = Regex::new(&format!(
+        r"(?i)\b{}\s*\(([^)]+)\)",
+        regex::escape(keyword)
+    )) {
+        if let Some(captures) = tag_regex.captures(text) {
+            let tag = captures.get(1).unwrap().as_str().trim();
+            if tag.starts_with('#') || tag.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+                issue_ref = Some(tag.to_string());
+            } else if !tag.is_empty() {
+                assignee = Some(tag.to_string());
+            }
+        }
+    }
+
+    if issue_ref.is_none() {
+        if let Ok(ticket_regex) = Regex::new(r"\b([A-Z]{2,}-\d+)\b") {
+            issue_ref = ticket_regex
+                .captures_iter(text)
+                .map(|c| c.get(1).unwrap().as_str().to_string())
+                .find(|candidate| {
+                    let prefix = candidate.split('-').next().unwrap_or("");
+                    !TICKET_FALSE_POSITIVES.contains(&prefix)
+                });
+        }
+    }
+
+    let due_date = Regex::new(r"\[(\d{4}-\d{2}-\d{2})\]")
+        .ok()
+        .and_then(|re| re.captures(text))
+        .map(|c| c.get(1).unwrap().as_str().to_string());
+
+    (assignee, issue_ref, due_date)
+}
+
 /// Scanner for finding TODO/FIXME entries in files
 pub struct TodoScanner {
     config: ScanConfig,
     keyword_regex: Regex,
+    pool: rayon::ThreadPool,
 }
 
 impl TodoScanner {
@@ -83,9 +402,23 @@ impl TodoScanner {
         let keyword_regex = Regex::new(&pattern)
             .with_context(|| "Failed to compile keyword regex")?;
 
+        let threads = if config.threads == 0 {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        } else {
+            config.threads
+        };
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .context("Failed to build scanner thread pool")?;
+
         Ok(Self {
             config,
             keyword_regex,
+            pool,
         })
     }
 
@@ -95,53 +428,248 @@ impl TodoScanner {
     }
 
     /// Scan a directory recursively
+    ///
+    /// Candidate files are discovered first, then handed to a rayon work-pool in
+    /// chunks so large trees scan with near-linear speedup across cores. The final
+    /// result is still sorted by `(file_path, line_number)`, so output stays
+    /// deterministic regardless of which worker finished first.
     pub async fn scan_directory(&self, path: impl AsRef<Path>) -> Result<Vec<TodoEntry>> {
         let path = path.as_ref();
-        let mut entries = Vec::new();
+        let candidates = self.collect_candidates(path)?;
+
+        let chunk_results: Vec<Vec<TodoEntry>> = self.pool.install(|| {
+            candidates
+                .par_chunks(SCAN_CHUNK_SIZE)
+                .map(|chunk| {
+                    let mut chunk_entries = Vec::new();
+                    for file_path in chunk {
+                        match self.scan_file_blocking(file_path) {
+                            Ok(file_entries) => chunk_entries.extend(file_entries),
+                            Err(e) => {
+                                eprintln!(
+                                    "Warning: Failed to scan {}: {}",
+                                    file_path.display(),
+                                    e
+                                );
+                            }
+                        }
+                    }
+                    chunk_entries
+                })
+                .collect()
+        });
+
+        let mut entries: Vec<TodoEntry> = chunk_results.into_iter().flatten().collect();
+
+        entries.sort_by(|a, b| {
+            a.file_path
+                .cmp(&b.file_path)
+                .then(a.line_number.cmp(&b.line_number))
+        });
+
+        Ok(entries)
+    }
 
-        let walker: Box<dyn Iterator<Item = Result<walkdir::DirEntry, walkdir::Error>>> = if self.config.respect_gitignore {
-            Box::new(
-                WalkDir::new(path)
-                    .follow_links(false)
-                    .into_iter()
-                    .filter_entry(|e| !self.is_ignored(e)),
-            )
+    /// Scan a directory the same way as [`scan_directory`](Self::scan_directory), but
+    /// fan out over the candidate files as a bounded concurrent async stream
+    /// (`futures::stream::buffer_unordered`) instead of a rayon work-pool. Prefer
+    /// this on I/O-bound workloads (slow/networked filesystems) where the limiting
+    /// factor is in-flight file reads rather than CPU; `ScanConfig.concurrency`
+    /// (`0` = available parallelism) caps how many files are open at once.
+    pub async fn scan_directory_buffered(&self, path: impl AsRef<Path>) -> Result<Vec<TodoEntry>> {
+        use futures::stream::{self, StreamExt};
+
+        let path = path.as_ref();
+        let candidates = self.collect_candidates(path)?;
+
+        let concurrency = if self.config.concurrency == 0 {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
         } else {
-            Box::new(
-                WalkDir::new(path)
-                    .follow_links(false)
-                    .into_iter(),
-            )
+            self.config.concurrency
         };
 
-        for entry in walker {
+        let mut entries: Vec<TodoEntry> = stream::iter(candidates)
+            .map(|file_path| async move {
+                match self.scan_file(&file_path).await {
+                    Ok(file_entries) => file_entries,
+                    Err(e) => {
+                        eprintln!("Warning: Failed to scan {}: {}", file_path.display(), e);
+                        Vec::new()
+                    }
+                }
+            })
+            .buffer_unordered(concurrency)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
+
+        entries.sort_by(|a, b| {
+            a.file_path
+                .cmp(&b.file_path)
+                .then(a.line_number.cmp(&b.line_number))
+        });
+
+        Ok(entries)
+    }
+
+    /// Walk `path` honoring gitignore/override/extension filters and return the list
+    /// of candidate files to scan, shared by [`scan_directory`](Self::scan_directory)
+    /// and [`scan_directory_buffered`](Self::scan_directory_buffered).
+    fn collect_candidates(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        let overrides = self.build_overrides(path)?;
+        let extra_ignore = self.build_extra_ignore(path)?;
+
+        let mut builder = WalkBuilder::new(path);
+        builder
+            .follow_links(false)
+            .git_ignore(self.config.respect_gitignore)
+            .git_global(self.config.respect_gitignore)
+            .git_exclude(self.config.respect_gitignore)
+            .ignore(self.config.respect_gitignore)
+            .overrides(overrides);
+
+        let mut candidates = Vec::new();
+        for entry in builder.build() {
             let entry = entry.with_context(|| "Failed to read directory entry")?;
 
-            if !entry.file_type().is_file() {
+            let is_file = entry.file_type().map(|t| t.is_file()).unwrap_or(false);
+            if !is_file {
                 continue;
             }
 
-            let file_path = entry.path();
+            if let Some(matcher) = &extra_ignore {
+                if matcher.matched_path_or_any_parents(entry.path(), false).is_ignore() {
+                    continue;
+                }
+            }
 
-            if !self.should_scan_file(file_path) {
-                continue;
+            if self.should_scan_file(entry.path()) {
+                candidates.push(entry.into_path());
             }
+        }
 
-            match self.scan_file(file_path).await {
-                Ok(file_entries) => entries.extend(file_entries),
-                Err(e) => {
-                    eprintln!("Warning: Failed to scan {}: {}", file_path.display(), e);
-                }
+        Ok(candidates)
+    }
+
+    /// Turn `exclude_patterns`/`include_patterns` into an `ignore` crate `Override`,
+    /// rooted at `path`, so that they're matched with real gitignore-style glob
+    /// semantics (via `WalkBuilder`) instead of a substring/regex hack.
+    ///
+    /// A pattern without glob metacharacters is widened to `*pattern*`, so a plain
+    /// keyword matches any file or directory name that contains it. Matching is per
+    /// name, not against the whole path string: an include keyword that appears only
+    /// in a parent directory's name does not include the files beneath it.
+    fn build_overrides(&self, path: &Path) -> Result<ignore::overrides::Override> {
+        let mut builder = OverrideBuilder::new(path);
+
+        for pattern in &self.config.exclude_patterns {
+            let glob = Self::as_glob(pattern);
+            builder
+                .add(&format!("!{}", glob))
+                .with_context(|| format!("Invalid exclude pattern: {}", pattern))?;
+        }
+
+        for pattern in &self.config.include_patterns {
+            let glob = Self::as_glob(pattern);
+            builder
+                .add(&glob)
+                .with_context(|| format!("Invalid include pattern: {}", pattern))?;
+        }
+
+        builder.build().context("Failed to build override rules")
+    }
+
+    /// Build a matcher for `ScanConfig.ignore_file`, a project-wide ignore list
+    /// applied on top of `.gitignore`/`.ignore`, rooted at `path` with full
+    /// gitignore glob semantics (negation, anchoring, `**`, directory-only rules).
+    fn build_extra_ignore(&self, path: &Path) -> Result<Option<ignore::gitignore::Gitignore>> {
+        let Some(ignore_file) = &self.config.ignore_file else {
+            return Ok(None);
+        };
+
+        let mut builder = ignore::gitignore::GitignoreBuilder::new(path);
+        if let Some(err) = builder.add(ignore_file) {
+            return Err(err).with_context(|| {
+                format!("Failed to read ignore file {}", ignore_file.display())
+            });
+        }
+
+        builder
+            .build()
+            .map(Some)
+            .context("Failed to build custom ignore matcher")
+    }
+
+    /// Widen a bare keyword into a `*keyword*` glob unless it already looks like one.
+    fn as_glob(pattern: &str) -> String {
+        if pattern.contains(['*', '?', '[']) {
+            pattern.to_string()
+        } else {
+            format!("*{}*", pattern)
+        }
+    }
+
+    /// Look for a configured keyword on `line`, honoring `comments_only` when the
+    /// file's extension has known comment syntax. `comment_state` must be threaded
+    /// line-by-line across a single file so multi-line block comments are tracked
+    /// correctly. A line can contain more than one keyword occurrence (e.g. one
+    /// inside a string literal and one inside a real comment), so every match is
+    /// checked against the comment spans and the first one that actually falls in
+    /// a comment is reported, rather than just the first match on the line.
+    /// Returns the matched keyword and, if resolved through comment detection, the
+    /// delimiter it was found under.
+    fn match_keyword(
+        &self,
+        line: &str,
+        syntax: Option<&'static LanguageSyntax>,
+        comment_state: &mut CommentState,
+    ) -> Option<(String, Option<&'static str>, Option<CommentKind>, usize)> {
+        let syntax = match syntax {
+            Some(syntax) => syntax,
+            // No known comment syntax for this extension (plain text, data
+            // formats, ...) - the whole line is fair game, as before.
+            None => {
+                let m = self.keyword_regex.captures(line)?.get(1)?;
+                return Some((m.as_str().to_string(), None, None, m.start()));
+            }
+        };
+
+        // `comment_spans` carries `comment_state` across lines, so it must run
+        // exactly once per line regardless of how many keyword matches follow.
+        let spans = comment_spans(line, syntax, comment_state);
+
+        let mut first_match = None;
+        for captures in self.keyword_regex.captures_iter(line) {
+            let Some(m) = captures.get(1) else { continue };
+            if first_match.is_none() {
+                first_match = Some(m);
+            }
+
+            if let Some((_, _, tok)) = spans
+                .iter()
+                .find(|(start, end, _)| m.start() >= *start && m.start() < *end)
+            {
+                let kind = if syntax.line_comments.contains(tok) {
+                    CommentKind::Line
+                } else {
+                    CommentKind::Block
+                };
+                return Some((m.as_str().to_string(), Some(*tok), Some(kind), m.start()));
             }
         }
 
-        entries.sort_by(|a, b| {
-            a.file_path
-                .cmp(&b.file_path)
-                .then(a.line_number.cmp(&b.line_number))
-        });
+        if self.config.comments_only {
+            return None;
+        }
 
-        Ok(entries)
+        // No match fell inside a comment span, but comments_only is off: fall back
+        // to the first match on the line, as before comment-aware detection existed.
+        let m = first_match?;
+        Some((m.as_str().to_string(), None, None, m.start()))
     }
 
     /// Scan a single file
@@ -160,87 +688,131 @@ impl TodoScanner {
             return Ok(Vec::new());
         }
 
-        let file = File::open(path)
+        let content = tokio::fs::read_to_string(path)
             .await
-            .with_context(|| format!("Failed to open {}", path.display()))?;
+            .with_context(|| format!("Failed to read {}", path.display()))?;
 
-        let reader = BufReader::new(file);
-        let mut lines = reader.lines();
-        let mut line_buffer: Vec<String> = Vec::with_capacity(self.config.context_lines * 2 + 1);
-        let mut entries = Vec::new();
-        let mut line_number: usize = 0;
+        Ok(self.scan_lines(path, &content))
+    }
 
-        while let Some(line) = lines
-            .next_line()
-            .await
-            .with_context(|| format!("Failed to read line from {}", path.display()))?
-        {
-            line_number += 1;
+    /// Synchronous counterpart to [`scan_file`](Self::scan_file), used by the rayon
+    /// work-pool in `scan_directory` since blocking file I/O is cheaper than
+    /// bouncing every file through the async executor from a worker thread.
+    fn scan_file_blocking(&self, path: &Path) -> Result<Vec<TodoEntry>> {
+        let metadata = std::fs::metadata(path)
+            .with_context(|| format!("Failed to read metadata for {}", path.display()))?;
+
+        if !metadata.is_file() {
+            return Ok(Vec::new());
+        }
+
+        let file_size = metadata.len() as usize;
+        if file_size > self.config.max_file_size {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        Ok(self.scan_lines(path, &content))
+    }
+
+    /// Core line-scanning pass shared by [`scan_file`](Self::scan_file) and
+    /// [`scan_file_blocking`](Self::scan_file_blocking). Reading the whole file up
+    /// front (bounded by `max_file_size`) lets [`build_context`](Self::build_context)
+    /// look both backward and forward from a match instead of only at prior lines.
+    fn scan_lines(&self, path: &Path, content: &str) -> Vec<TodoEntry> {
+        let syntax = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .and_then(language_syntax);
+        let mut comment_state = CommentState::default();
+        let lines: Vec<&str> = content.lines().collect();
+        let mut entries = Vec::new();
 
+        for (idx, line) in lines.iter().enumerate() {
             if line.trim().is_empty() {
                 continue;
             }
 
-            if let Some(captures) = self.keyword_regex.captures(&line) {
-                let keyword = captures
-                    .get(1)
-                    .map(|m| m.as_str().to_string())
-                    .unwrap_or_default();
-
+            if let Some((keyword, comment_style, comment_kind, raw_column)) =
+                self.match_keyword(line, syntax, &mut comment_state)
+            {
                 let text = line.trim().to_string();
+                let leading_whitespace = line.len() - line.trim_start().len();
 
-                let mut entry = TodoEntry::new(path, line_number, keyword, text);
+                let mut entry = TodoEntry::new(path, idx + 1, &keyword, text);
+                entry.column = raw_column.saturating_sub(leading_whitespace);
 
-                if self.config.context_lines > 0 {
-                    let context = self.build_context(&line_buffer, &line);
-                    entry = entry.with_context(context);
+                if let Some(style) = comment_style {
+                    entry = entry.with_comment_style(style);
                 }
+                entry.comment_kind = comment_kind;
 
-                entries.push(entry);
-            }
+                let (assignee, issue_ref, due_date) = parse_annotations(&entry.text, &keyword);
+                entry.assignee = assignee;
+                entry.issue_ref = issue_ref;
+                entry.due_date = due_date;
 
-            if self.config.context_lines > 0 {
-                line_buffer.push(line.clone());
-                if line_buffer.len() > self.config.context_lines * 2 + 1 {
-                    line_buffer.remove(0);
+                if self.config.context_lines > 0 {
+                    let (context, context_start_line) = self.build_context(&lines, idx);
+                    entry = entry.with_context(context, context_start_line);
                 }
+
+                if !self.passes_annotation_filters(&entry) {
+                    continue;
+                }
+
+                entries.push(entry);
             }
         }
 
-        Ok(entries)
+        entries
     }
 
-    fn build_context(&self, _buffer: &[String], current_line: &str) -> String {
-        // For now, just return the current line as context
-        // Full context implementation would join multiple lines
-        current_line.to_string()
+    /// Join `context_lines` lines of source on either side of `lines[idx]`, clamped
+    /// to the file's bounds, along with the 1-based line number of the first line
+    /// returned (needed since clamping near the start of a file means fewer than
+    /// `context_lines` lines may precede the match).
+    fn build_context(&self, lines: &[&str], idx: usize) -> (String, usize) {
+        let span = self.config.context_lines;
+        let start = idx.saturating_sub(span);
+        let end = (idx + span + 1).min(lines.len());
+        (lines[start..end].join("\n"), start + 1)
     }
 
-    fn is_ignored(&self, entry: &walkdir::DirEntry) -> bool {
-        let path = entry.path();
-
-        if path
-            .file_name()
-            .map(|n| n == ".git")
-            .unwrap_or(false)
-        {
-            return true;
+    /// Apply `ScanConfig.assignee_filter`/`ScanConfig.require_issue_ref` to a scanned
+    /// entry. Entries are kept unless a configured filter is set and the entry fails it.
+    fn passes_annotation_filters(&self, entry: &TodoEntry) -> bool {
+        if let Some(assignee) = &self.config.assignee_filter {
+            match &entry.assignee {
+                Some(entry_assignee) if entry_assignee.eq_ignore_ascii_case(assignee) => {}
+                _ => return false,
+            }
         }
 
-        if self.config.respect_gitignore {
-            if let Some(parent) = path.parent() {
-                if parent.join(".gitignore").exists() {
-                    // Simple heuristic: check if path matches common ignore patterns
-                    if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                        if name.starts_with('.') || name.contains("node_modules") {
-                            return true;
-                        }
-                    }
-                }
+        if let Some(require_issue_ref) = self.config.require_issue_ref {
+            if entry.issue_ref.is_some() != require_issue_ref {
+                return false;
             }
         }
 
-        false
+        true
+    }
+
+    /// Returns true if `path` is not named `.git`, has a supported extension (or a
+    /// known build-file name), and contains none of the configured exclude patterns
+    /// as a substring. Unlike [`scan_directory`](Self::scan_directory), it does not
+    /// consult `.gitignore`/`.ignore` files, `ignore_file`, or the glob-based
+    /// include/exclude overrides, so it can accept a path that a directory scan skips.
+    ///
+    /// Useful for callers (such as a watch loop) that observe individual filesystem
+    /// events and need to decide whether a changed path warrants a rescan.
+    pub fn should_include_path(&self, path: &Path) -> bool {
+        if path.file_name().map(|n| n == ".git").unwrap_or(false) {
+            return false;
+        }
+        self.should_scan_file(path)
     }
 
     fn should_scan_file(&self, path: &Path) -> bool {
@@ -270,29 +842,111 @@ impl TodoScanner {
             return false;
         }
 
-        // Check exclude patterns
+        // Literal exclude fallback, kept as an additional filter layer on top of
+        // `build_overrides`/`WalkBuilder` for callers (e.g. the watch loop, via
+        // `should_include_path`) that check a single path outside a directory walk
+        // and so never run through the override-based glob matching at all. Unlike
+        // the old include whitelist this removed, a substring miss here doesn't
+        // reject the file - it just doesn't exclude it - so it can't zero out results.
         for pattern in &self.config.exclude_patterns {
-            if let Ok(regex) = Regex::new(&format!(".*{}.*", regex::escape(pattern))) {
-                if regex.is_match(&path.to_string_lossy()) {
-                    return false;
-                }
+            if path.to_string_lossy().contains(pattern.as_str()) {
+                return false;
             }
         }
 
-        // Check include patterns
-        if !self.config.include_patterns.is_empty() {
-            let path_str = path.to_string_lossy();
-            for pattern in &self.config.include_patterns {
-                if let Ok(regex) = Regex::new(&format!(".*{}.*", regex::escape(pattern))) {
-                    if regex.is_match(&path_str) {
-                        return true;
+        true
+    }
+}
+
+impl TodoScanner {
+    /// Perform an initial [`scan_directory`](Self::scan_directory) of `path`, then
+    /// watch it for filesystem changes and call `on_update` with the re-scanned
+    /// entries of every batch of changed files that yields at least one entry, until
+    /// the process is interrupted. Errors the watcher reports as events are skipped;
+    /// only setup failures and the initial scan return an error.
+    ///
+    /// Filesystem events arriving within [`WATCH_DEBOUNCE`] of each other are
+    /// coalesced into a single rescan, and changed paths are filtered through
+    /// [`should_include_path`](Self::should_include_path) so `.git` and paths
+    /// containing an exclude pattern don't trigger noise. `path` is canonicalized
+    /// up front so the watch survives the process's current directory changing.
+    pub async fn watch<F>(&self, path: impl AsRef<Path>, mut on_update: F) -> Result<()>
+    where
+        F: FnMut(Vec<TodoEntry>),
+    {
+        let path = path
+            .as_ref()
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {}", path.as_ref().display()))?;
+
+        on_update(self.scan_directory(&path).await?);
+
+        let (tx, rx) = std::sync::mpsc::channel::<notify::Result<notify::Event>>();
+
+        let mut watcher = notify::recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        })
+        .context("Failed to create filesystem watcher")?;
+
+        notify::Watcher::watch(&mut watcher, &path, notify::RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {}", path.display()))?;
+
+        loop {
+            let first = match rx.recv() {
+                Ok(event) => event,
+                Err(_) => return Ok(()),
+            };
+
+            let mut changed: std::collections::HashSet<PathBuf> = std::collections::HashSet::new();
+            collect_changed_paths(first, &mut changed);
+
+            while let Ok(event) = rx.recv_timeout(WATCH_DEBOUNCE) {
+                collect_changed_paths(event, &mut changed);
+            }
+
+            let mut entries = Vec::new();
+            for changed_path in &changed {
+                if !self.should_include_path(changed_path) {
+                    continue;
+                }
+
+                if changed_path.is_dir() {
+                    if let Ok(found) = self.scan_directory(changed_path).await {
+                        entries.extend(found);
+                    }
+                } else if changed_path.is_file() {
+                    if let Ok(found) = self.scan_file(changed_path).await {
+                        entries.extend(found);
                     }
                 }
             }
-            return false;
+
+            if entries.is_empty() {
+                continue;
+            }
+
+            entries.sort_by(|a, b| {
+                a.file_path
+                    .cmp(&b.file_path)
+                    .then(a.line_number.cmp(&b.line_number))
+            });
+
+            on_update(entries);
         }
+    }
+}
 
-        true
+/// Debounce window for coalescing bursts of filesystem events into a single rescan,
+/// shared by [`TodoScanner::watch`].
+const WATCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(150);
+
+/// Collect every path touched by a filesystem event into `changed`, ignoring events
+/// the watcher failed to decode.
+fn collect_changed_paths(event: notify::Result<notify::Event>, changed: &mut std::collections::HashSet<PathBuf>) {
+    if let Ok(event) = event {
+        for path in event.paths {
+            changed.insert(path);
+        }
     }
 }
 
@@ -342,6 +996,20 @@ pub fn format_results_colored(entries: &[TodoEntry], show_context: bool) -> Stri
 
         output.push_str(&format!("{} {}\n", line_num, text_colored));
 
+        if entry.assignee.is_some() || entry.issue_ref.is_some() || entry.due_date.is_some() {
+            let mut tags = Vec::new();
+            if let Some(assignee) = &entry.assignee {
+                tags.push(format!("@{}", assignee).magenta().to_string());
+            }
+            if let Some(issue_ref) = &entry.issue_ref {
+                tags.push(issue_ref.blue().to_string());
+            }
+            if let Some(due_date) = &entry.due_date {
+                tags.push(format!("due {}", due_date).dimmed().to_string());
+            }
+            output.push_str(&format!("      {}\n", tags.join("  ")));
+        }
+
         if show_context && entry.context.is_some() {
             output.push_str(&format!("{}\n", "    ...".dimmed()));
         }
@@ -363,6 +1031,259 @@ pub fn format_results_json(entries: &[TodoEntry]) -> Result<String> {
     serde_json::to_string_pretty(entries).context("Failed to serialize results to JSON")
 }
 
+/// Format results as a SARIF 2.1.0 log, suitable for upload to GitHub code scanning
+/// and other SARIF-consuming dashboards. Each distinct keyword becomes its own rule
+/// (`ruleId`), and each entry becomes a `result` pointing at its file and line.
+pub fn format_results_sarif(entries: &[TodoEntry]) -> Result<String> {
+    let mut rule_ids: Vec<String> = Vec::new();
+    for entry in entries {
+        let rule_id = entry.keyword.to_uppercase();
+        if !rule_ids.contains(&rule_id) {
+            rule_ids.push(rule_id);
+        }
+    }
+
+    let rules: Vec<SarifRule> = rule_ids
+        .iter()
+        .map(|id| SarifRule {
+            id: id.clone(),
+            name: id.clone(),
+        })
+        .collect();
+
+    let results: Vec<SarifResult> = entries
+        .iter()
+        .map(|entry| SarifResult {
+            rule_id: entry.keyword.to_uppercase(),
+            message: SarifMessage {
+                text: entry.text.clone(),
+            },
+            locations: vec![SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation {
+                        uri: entry.file_path.display().to_string(),
+                    },
+                    region: SarifRegion {
+                        start_line: entry.line_number,
+                        snippet: entry.context.as_ref().map(|context| SarifSnippet {
+                            text: context.clone(),
+                        }),
+                    },
+                },
+            }],
+        })
+        .collect();
+
+    let log = SarifLog {
+        schema: "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json".to_string(),
+        version: "2.1.0".to_string(),
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "todo-scan".to_string(),
+                    version: "1.0.0".to_string(),
+                    rules,
+                },
+            },
+            results,
+        }],
+    };
+
+    serde_json::to_string_pretty(&log).context("Failed to serialize SARIF log")
+}
+
+#[derive(Debug, Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: String,
+    version: String,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifDriver {
+    name: String,
+    version: String,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifRule {
+    id: String,
+    name: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifResult {
+    #[serde(rename = "ruleId")]
+    rule_id: String,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifLocation {
+    #[serde(rename = "physicalLocation")]
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifPhysicalLocation {
+    #[serde(rename = "artifactLocation")]
+    artifact_location: SarifArtifactLocation,
+    region: SarifRegion,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifRegion {
+    #[serde(rename = "startLine")]
+    start_line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snippet: Option<SarifSnippet>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifSnippet {
+    text: String,
+}
+
+/// Annotate every entry with the commit that last touched its line: author, short
+/// commit hash, and commit date. Lookups are batched per file (one `git blame` pass
+/// over the whole file, not one per matched line) since entries are typically
+/// clustered a handful to a file.
+pub fn attach_blame(entries: &mut [TodoEntry]) -> Result<()> {
+    let mut lines_by_file: HashMap<PathBuf, Vec<usize>> = HashMap::new();
+    for entry in entries.iter() {
+        lines_by_file
+            .entry(entry.file_path.clone())
+            .or_default()
+            .push(entry.line_number);
+    }
+
+    let mut blamed: HashMap<(PathBuf, usize), blame::BlameInfo> = HashMap::new();
+    for (file, lines) in lines_by_file {
+        match blame::blame_lines(&file, &lines) {
+            Ok(info) => {
+                for (line, b) in info {
+                    blamed.insert((file.clone(), line), b);
+                }
+            }
+            Err(e) => {
+                eprintln!("Warning: Failed to blame {}: {}", file.display(), e);
+            }
+        }
+    }
+
+    for entry in entries.iter_mut() {
+        if let Some(info) = blamed.get(&(entry.file_path.clone(), entry.line_number)) {
+            entry.author = Some(info.author.clone());
+            entry.commit = Some(info.commit.clone());
+            entry.committed_at = Some(info.committed_at.clone());
+        }
+    }
+
+    Ok(())
+}
+
+/// Keep only entries whose blamed commit (see [`attach_blame`]) is older than
+/// `max_age`. Entries without blame information are kept, since we have no age to
+/// compare against.
+pub fn filter_older_than(entries: Vec<TodoEntry>, max_age: std::time::Duration) -> Vec<TodoEntry> {
+    let cutoff = chrono::Utc::now() - chrono::Duration::from_std(max_age).unwrap_or_default();
+
+    entries
+        .into_iter()
+        .filter(|entry| match &entry.committed_at {
+            Some(committed_at) => chrono::DateTime::parse_from_rfc3339(committed_at)
+                .map(|dt| dt < cutoff)
+                .unwrap_or(true),
+            None => true,
+        })
+        .collect()
+}
+
+/// Format results as compiler-style annotated snippets, the way rustc/cargo present
+/// diagnostics: a gutter with the line number, the offending source line, and a
+/// caret under the matched keyword labelled with the comment text. FIXME renders as
+/// an error-level annotation, TODO (and everything else) as a warning.
+pub fn format_results_pretty(entries: &[TodoEntry]) -> Result<String> {
+    use annotate_snippets::{Level, Renderer, Snippet};
+
+    let renderer = Renderer::styled();
+    let mut output = String::new();
+
+    for entry in entries {
+        let level = if entry.keyword.eq_ignore_ascii_case("FIXME") {
+            Level::Error
+        } else {
+            Level::Warning
+        };
+
+        let origin = entry.file_path.display().to_string();
+
+        // When `--context` was requested, render the full surrounding source instead
+        // of just the matched line, with the annotation re-anchored to the matched
+        // line's position (and its own leading whitespace, since `entry.column` is
+        // relative to the *trimmed* line) within that block.
+        let (source, line_start, span_start, span_end) =
+            match (entry.context.as_deref(), entry.context_start_line) {
+                (Some(context), Some(context_start_line)) => {
+                    let context_lines: Vec<&str> = context.split('\n').collect();
+                    let matched_idx = entry.line_number.saturating_sub(context_start_line);
+                    let matched_line = context_lines.get(matched_idx).copied().unwrap_or("");
+                    let leading_whitespace = matched_line.len() - matched_line.trim_start().len();
+                    let line_offset: usize = context_lines[..matched_idx.min(context_lines.len())]
+                        .iter()
+                        .map(|l| l.len() + 1)
+                        .sum();
+
+                    let start = (line_offset + leading_whitespace + entry.column).min(context.len());
+                    let end = (start + entry.keyword.len()).min(context.len());
+                    (context, context_start_line, start, end)
+                }
+                _ => {
+                    let start = entry.column.min(entry.text.len());
+                    let end = (entry.column + entry.keyword.len()).min(entry.text.len());
+                    (entry.text.as_str(), entry.line_number, start, end)
+                }
+            };
+
+        let message = level.title(&entry.text).snippet(
+            Snippet::source(source)
+                .line_start(line_start)
+                .origin(&origin)
+                .fold(false)
+                .annotation(level.span(span_start..span_end).label(&entry.text)),
+        );
+
+        output.push_str(&renderer.render(message).to_string());
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -384,4 +1305,166 @@ mod tests {
         assert!(config.exclude_patterns.is_empty());
         assert_eq!(config.max_file_size, 10 * 1024 * 1024);
     }
+
+    #[test]
+    fn test_find_string_end_skips_escaped_quote() {
+        let line = "\"ab\\\"cd\" rest";
+        assert_eq!(find_string_end(line, 0, '"'), 8);
+    }
+
+    #[test]
+    fn test_find_string_end_unterminated_returns_line_len() {
+        let line = "\"unterminated";
+        assert_eq!(find_string_end(line, 0, '"'), line.len());
+    }
+
+    #[test]
+    fn test_comment_spans_skips_keyword_in_string_literal() {
+        let syntax = language_syntax("rs").unwrap();
+        let mut state = CommentState::default();
+        let line = "let s = \"// not a comment\"; // real";
+
+        let spans = comment_spans(line, syntax, &mut state);
+
+        assert_eq!(spans.len(), 1);
+        let (start, end, tok) = spans[0];
+        assert_eq!(tok, "//");
+        assert_eq!(end, line.len());
+        assert_eq!(start, line.rfind("//").unwrap());
+    }
+
+    #[test]
+    fn test_comment_spans_mid_line_block_comment() {
+        let syntax = language_syntax("rs").unwrap();
+        let mut state = CommentState::default();
+        let line = "do_thing(); /* TODO fix */ other();";
+
+        let spans = comment_spans(line, syntax, &mut state);
+
+        assert_eq!(spans.len(), 1);
+        let (start, end, tok) = spans[0];
+        assert_eq!(tok, "/*");
+        assert_eq!(&line[start..end], "/* TODO fix */");
+        assert!(state.open_block.is_none());
+    }
+
+    #[test]
+    fn test_comment_spans_unterminated_block_carries_across_lines() {
+        let syntax = language_syntax("rs").unwrap();
+        let mut state = CommentState::default();
+
+        let spans1 = comment_spans("/* TODO unterminated", syntax, &mut state);
+        assert_eq!(spans1, vec![(0, "/* TODO unterminated".len(), "/*")]);
+        assert!(state.open_block.is_some());
+
+        let spans2 = comment_spans("still inside TODO too", syntax, &mut state);
+        assert_eq!(spans2, vec![(0, "still inside TODO too".len(), "*/")]);
+        assert!(state.open_block.is_some());
+
+        let line3 = "end */ after";
+        let spans3 = comment_spans(line3, syntax, &mut state);
+        assert_eq!(spans3, vec![(0, 6, "*/")]);
+        assert_eq!(&line3[0..6], "end */");
+        assert!(state.open_block.is_none());
+    }
+
+    #[test]
+    fn test_comment_spans_hash_comment_with_apostrophe() {
+        let syntax = language_syntax("py").unwrap();
+        let mut state = CommentState::default();
+        let line = "# TODO: don't forget";
+
+        let spans = comment_spans(line, syntax, &mut state);
+
+        assert_eq!(spans, vec![(0, line.len(), "#")]);
+    }
+
+    #[test]
+    fn test_comment_spans_lua_block_comment_not_mistaken_for_line_comment() {
+        let syntax = language_syntax("lua").unwrap();
+        let mut state = CommentState::default();
+
+        let spans1 = comment_spans("--[[ TODO multi-line", syntax, &mut state);
+        assert_eq!(spans1, vec![(0, "--[[ TODO multi-line".len(), "--[[")]);
+        assert!(state.open_block.is_some());
+
+        let spans2 = comment_spans("still TODO here", syntax, &mut state);
+        assert_eq!(spans2, vec![(0, "still TODO here".len(), "]]")]);
+        assert!(state.open_block.is_some());
+
+        let line3 = "done ]] after";
+        let spans3 = comment_spans(line3, syntax, &mut state);
+        assert_eq!(spans3, vec![(0, 7, "]]")]);
+        assert_eq!(&line3[0..7], "done ]]");
+        assert!(state.open_block.is_none());
+    }
+
+    #[test]
+    fn test_scan_lines_reports_comment_match_after_string_false_positive() {
+        let scanner = TodoScanner::new(ScanConfig {
+            comments_only: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let content = "let s = \"TODO later\"; // TODO real\n";
+        let entries = scanner.scan_lines(Path::new("test.rs"), content);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].comment_style.as_deref(), Some("//"));
+        assert_eq!(entries[0].comment_kind, Some(CommentKind::Line));
+    }
+
+    #[test]
+    fn test_scan_lines_comments_only_drops_string_and_code_matches() {
+        let scanner = TodoScanner::new(ScanConfig {
+            comments_only: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let content = "let s = \"TODO in string\";\nlet TODO = 1;\n";
+        let entries = scanner.scan_lines(Path::new("test.rs"), content);
+
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_scan_lines_multiline_block_comment_across_lines() {
+        let scanner = TodoScanner::new(ScanConfig {
+            comments_only: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let content = "/* TODO unterminated\nstill inside TODO too\nend */ TODO after\n";
+        let entries = scanner.scan_lines(Path::new("test.rs"), content);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].line_number, 1);
+        assert_eq!(entries[1].line_number, 2);
+        assert!(entries.iter().all(|e| e.comment_kind == Some(CommentKind::Block)));
+    }
+
+    #[test]
+    fn test_parse_annotations_ignores_tech_abbreviations() {
+        let (_, issue_ref, _) = parse_annotations("TODO: decode as UTF-8", "TODO");
+        assert_eq!(issue_ref, None);
+
+        let (_, issue_ref, _) = parse_annotations("FIXME: hash with SHA-1 for now", "FIXME");
+        assert_eq!(issue_ref, None);
+
+        let (_, issue_ref, _) = parse_annotations("TODO: parse ISO-8601 timestamps", "TODO");
+        assert_eq!(issue_ref, None);
+    }
+
+    #[test]
+    fn test_parse_annotations_still_finds_real_ticket_refs() {
+        let (_, issue_ref, _) = parse_annotations("TODO: see JIRA-456 for details", "TODO");
+        assert_eq!(issue_ref.as_deref(), Some("JIRA-456"));
+
+        let (_, issue_ref, _) =
+            parse_annotations("FIXME: mentions UTF-8 but tracked as PROJ-12", "FIXME");
+        assert_eq!(issue_ref.as_deref(), Some("PROJ-12"));
+    }
 }
\ No newline at end of file
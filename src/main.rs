@@ -1,9 +1,19 @@
 use anyhow::{Context, Result};
 use clap::{Parser, ValueEnum};
 use colored::Colorize;
+use notify::{RecursiveMode, Watcher};
+use std::collections::HashSet;
 use std::path::PathBuf;
+use std::sync::mpsc::channel;
+use std::time::Duration;
 
-use todo_scan::{format_results_colored, format_results_json, ScanConfig, TodoScanner};
+use todo_scan::{
+    attach_blame, filter_older_than, format_results_colored, format_results_json,
+    format_results_pretty, format_results_sarif, ScanConfig, TodoScanner,
+};
+
+/// Debounce window for coalescing bursts of filesystem events into a single rescan.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(100);
 
 #[derive(Parser, Debug)]
 #[command(
@@ -52,12 +62,89 @@ struct Cli {
     /// Suppress informational output
     #[arg(short, long)]
     quiet: bool,
+
+    /// Stay running and re-scan whenever watched files change
+    #[arg(short = 'w', long)]
+    watch: bool,
+
+    /// When watching, only watch the top-level directory instead of descending recursively
+    #[arg(short = 'W', long = "no-recursive")]
+    no_recursive: bool,
+
+    /// Number of threads to use for directory scans (default: available parallelism)
+    #[arg(long, default_value = "0")]
+    threads: usize,
+
+    /// Match keywords only inside comments (the default; pass --all-lines instead
+    /// to match anywhere on a line). Accepted for symmetry with --all-lines; has no
+    /// effect beyond making the default explicit.
+    #[arg(long, conflicts_with = "all_lines")]
+    comments_only: bool,
+
+    /// Match keywords anywhere on a line, not just inside comments
+    #[arg(long, conflicts_with = "comments_only")]
+    all_lines: bool,
+
+    /// Annotate each entry with its git blame author, commit, and date
+    #[arg(long)]
+    blame: bool,
+
+    /// Only keep entries (requires --blame) whose blamed commit is older than this,
+    /// e.g. "30d", "6months"
+    #[arg(long, value_name = "DURATION", requires = "blame")]
+    older_than: Option<String>,
+
+    /// Inject an owner tag into bare TODOs: `TODO:` becomes `TODO(name):`
+    #[arg(long, value_name = "NAME")]
+    assign: Option<String>,
+
+    /// Delete comment lines matching this keyword
+    #[arg(long, value_name = "KEYWORD")]
+    strip: Option<String>,
+
+    /// Emit a JSON report of issue drafts instead of scanning output
+    #[arg(long)]
+    promote: bool,
+
+    /// Preview --assign/--strip edits as a diff instead of writing them
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Additional gitignore-style file to apply on top of .gitignore/.ignore
+    #[arg(long, value_name = "PATH")]
+    ignore_file: Option<PathBuf>,
+
+    /// Only show TODOs assigned to this owner, e.g. `TODO(alice):`
+    #[arg(long, value_name = "NAME")]
+    assignee: Option<String>,
+
+    /// Only show TODOs that have an issue/ticket reference attached
+    #[arg(long, conflicts_with = "unassigned")]
+    has_issue_ref: bool,
+
+    /// Only show TODOs that lack an issue/ticket reference (unowned/untracked)
+    #[arg(long, conflicts_with = "has_issue_ref")]
+    unassigned: bool,
+
+    /// Scan directories as a bounded concurrent async stream instead of a rayon
+    /// work-pool; better suited to slow/networked filesystems
+    #[arg(long)]
+    buffered: bool,
+
+    /// Maximum number of files scanned concurrently with --buffered (default:
+    /// available parallelism)
+    #[arg(long, default_value = "0")]
+    concurrency: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 enum OutputFormat {
     Text,
     Json,
+    /// Compiler-style annotated snippets, as rendered by `annotate-snippets`.
+    Pretty,
+    /// SARIF 2.1.0, for GitHub code scanning and other SARIF-consuming dashboards.
+    Sarif,
 }
 
 #[tokio::main]
@@ -102,6 +189,20 @@ async fn main() -> Result<()> {
     config.context_lines = cli.context;
     config.respect_gitignore = !cli.no_gitignore;
     config.max_file_size = cli.max_size * 1024 * 1024;
+    config.threads = cli.threads;
+    // --comments-only is just the explicit spelling of the default; --all-lines is
+    // the only flag that actually changes this.
+    config.comments_only = !cli.all_lines;
+    config.ignore_file = cli.ignore_file.clone();
+    config.assignee_filter = cli.assignee.clone();
+    config.require_issue_ref = if cli.has_issue_ref {
+        Some(true)
+    } else if cli.unassigned {
+        Some(false)
+    } else {
+        None
+    };
+    config.concurrency = cli.concurrency;
 
     if !cli.quiet {
         eprintln!("{}", "todo-scan v1.0.0".bold());
@@ -138,7 +239,12 @@ async fn main() -> Result<()> {
                 }
             }
         } else if path.is_dir() {
-            match scanner.scan_directory(path).await {
+            let result = if cli.buffered {
+                scanner.scan_directory_buffered(path).await
+            } else {
+                scanner.scan_directory(path).await
+            };
+            match result {
                 Ok(entries) => all_entries.extend(entries),
                 Err(e) => {
                     if !cli.quiet {
@@ -161,6 +267,33 @@ async fn main() -> Result<()> {
         }
     }
 
+    if cli.blame {
+        attach_blame(&mut all_entries).context("Failed to attach git blame")?;
+    }
+
+    if let Some(older_than) = &cli.older_than {
+        let max_age = humantime::parse_duration(older_than)
+            .with_context(|| format!("Invalid duration for --older-than: {}", older_than))?;
+        all_entries = filter_older_than(all_entries, max_age);
+    }
+
+    if cli.promote {
+        let report =
+            todo_scan::fix::promote_report(&all_entries).context("Failed to build promote report")?;
+        println!("{}", report);
+        return Ok(());
+    }
+
+    if let Some(name) = &cli.assign {
+        run_fix(&all_entries, todo_scan::fix::FixKind::Assign(name.clone()), cli.dry_run)?;
+        return Ok(());
+    }
+
+    if let Some(keyword) = &cli.strip {
+        run_fix(&all_entries, todo_scan::fix::FixKind::Strip(keyword.clone()), cli.dry_run)?;
+        return Ok(());
+    }
+
     // Output results
     match cli.format {
         OutputFormat::Text => {
@@ -171,6 +304,19 @@ async fn main() -> Result<()> {
             let output = format_results_json(&all_entries).context("Failed to format JSON")?;
             println!("{}", output);
         }
+        OutputFormat::Pretty => {
+            let output = format_results_pretty(&all_entries).context("Failed to format snippets")?;
+            println!("{}", output);
+        }
+        OutputFormat::Sarif => {
+            let output = format_results_sarif(&all_entries).context("Failed to format SARIF")?;
+            println!("{}", output);
+        }
+    }
+
+    if cli.watch {
+        run_watch(&scanner, &cli.paths, cli.format, !cli.no_recursive, cli.quiet).await?;
+        return Ok(());
     }
 
     // Exit with appropriate code
@@ -180,3 +326,151 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+/// Read the original content of every file touched by `entries`, plan the edits
+/// implied by `kind`, and either print them as a diff (`dry_run`) or apply them to
+/// disk atomically.
+fn run_fix(entries: &[todo_scan::TodoEntry], kind: todo_scan::fix::FixKind, dry_run: bool) -> Result<()> {
+    use std::collections::HashMap;
+
+    let mut contents: HashMap<std::path::PathBuf, String> = HashMap::new();
+    for entry in entries {
+        if !contents.contains_key(&entry.file_path) {
+            let content = std::fs::read_to_string(&entry.file_path)
+                .with_context(|| format!("Failed to read {}", entry.file_path.display()))?;
+            contents.insert(entry.file_path.clone(), content);
+        }
+    }
+
+    let edits = todo_scan::fix::plan_edits(entries, &contents, &kind);
+
+    if edits.is_empty() {
+        eprintln!("{}", "No matching lines to edit.".dimmed());
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("{}", todo_scan::fix::render_diff(&edits));
+    } else {
+        todo_scan::fix::apply_edits(&contents, &edits)?;
+        eprintln!(
+            "{}",
+            format!(
+                "Edited {} line(s) across {} file(s).",
+                edits.len(),
+                edits.iter().map(|e| &e.file_path).collect::<HashSet<_>>().len()
+            )
+            .green()
+        );
+    }
+
+    Ok(())
+}
+
+/// Watch `paths` for filesystem changes and re-scan affected files as they occur.
+///
+/// Events are debounced (`WATCH_DEBOUNCE`) so that a burst of saves from an editor
+/// only triggers a single rescan of the touched files, and are filtered through
+/// `TodoScanner::should_include_path` (extension and literal exclude checks; no
+/// gitignore or include rules) so temp files don't cause noise.
+async fn run_watch(
+    scanner: &TodoScanner,
+    paths: &[PathBuf],
+    format: OutputFormat,
+    recursive: bool,
+    quiet: bool,
+) -> Result<()> {
+    let (tx, rx) = channel::<notify::Result<notify::Event>>();
+
+    let mut watcher = notify::recommended_watcher(move |res| {
+        // Best-effort: a disconnected receiver just drops the event.
+        let _ = tx.send(res);
+    })
+    .context("Failed to create filesystem watcher")?;
+
+    let mode = if recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+
+    for path in paths {
+        watcher
+            .watch(path, mode)
+            .with_context(|| format!("Failed to watch {}", path.display()))?;
+    }
+
+    if !quiet {
+        eprintln!("{}", "Watching for changes... (Ctrl-C to stop)".dimmed());
+    }
+
+    loop {
+        // Block for the first event, then drain anything else that arrives within
+        // the debounce window so a burst of saves collapses into one rescan.
+        let first = match rx.recv() {
+            Ok(event) => event,
+            Err(_) => return Ok(()),
+        };
+
+        let mut changed: HashSet<PathBuf> = HashSet::new();
+        collect_changed_paths(first, &mut changed);
+
+        while let Ok(event) = rx.recv_timeout(WATCH_DEBOUNCE) {
+            collect_changed_paths(event, &mut changed);
+        }
+
+        let mut entries = Vec::new();
+        for path in &changed {
+            if !scanner.should_include_path(path) {
+                continue;
+            }
+
+            if path.is_dir() {
+                if let Ok(found) = scanner.scan_directory(path).await {
+                    entries.extend(found);
+                }
+            } else if path.is_file() {
+                if let Ok(found) = scanner.scan_file(path).await {
+                    entries.extend(found);
+                }
+            }
+        }
+
+        entries.sort_by(|a: &todo_scan::TodoEntry, b| {
+            a.file_path
+                .cmp(&b.file_path)
+                .then(a.line_number.cmp(&b.line_number))
+        });
+
+        if entries.is_empty() {
+            continue;
+        }
+
+        match format {
+            OutputFormat::Text => println!("{}", format_results_colored(&entries, false)),
+            OutputFormat::Json => {
+                if let Ok(output) = format_results_json(&entries) {
+                    println!("{}", output);
+                }
+            }
+            OutputFormat::Pretty => {
+                if let Ok(output) = format_results_pretty(&entries) {
+                    println!("{}", output);
+                }
+            }
+            OutputFormat::Sarif => {
+                if let Ok(output) = format_results_sarif(&entries) {
+                    println!("{}", output);
+                }
+            }
+        }
+    }
+}
+
+fn collect_changed_paths(event: notify::Result<notify::Event>, changed: &mut HashSet<PathBuf>) {
+    if let Ok(event) = event {
+        for path in event.paths {
+            changed.insert(path);
+        }
+    }
+}